@@ -1,13 +1,159 @@
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use serde::{Deserialize, Serialize};
+use tauri::Emitter;
 
-#[derive(Serialize, Deserialize, Debug)]
+// In-memory cache of previously scanned project trees, keyed by the root
+// folder path plus filtering options (see `scan_cache_key`), so repeated
+// `get_folder_structure` calls don't have to re-read disk.
+// `invalidate_scan_cache` drops every cached tree for one root; subtrees
+// cannot be invalidated on their own.
+struct ProjectIndex(Mutex<HashMap<String, Vec<FileNode>>>);
+
+// Canonicalized roots the fs commands are allowed to touch, set up via
+// `add_allowed_path` when a project is opened. Empty means nothing is in
+// scope yet, so every fs command is rejected until a project opens one up.
+struct AllowedPaths(Mutex<Vec<PathBuf>>);
+
+// Resolves `path` to a canonical, symlink-free form and checks it falls
+// under one of the allowed roots, rejecting anything that escapes the
+// project scope via `..` traversal or a symlink pointing outside it. Since
+// the path may not exist yet (e.g. a file about to be created), this walks
+// up to the closest existing ancestor, canonicalizes that, and re-appends
+// the non-existent tail.
+fn resolve_within_scope(path: &Path, allowed: &tauri::State<'_, AllowedPaths>) -> Result<PathBuf, String> {
+    let roots = allowed.0.lock().unwrap();
+    if roots.is_empty() {
+        return Err("No project scope has been set; open a project first".to_string());
+    }
+
+    let mut existing = path;
+    let mut missing_tail: Vec<std::ffi::OsString> = Vec::new();
+
+    let canonical_existing = loop {
+        match existing.canonicalize() {
+            Ok(canonical) => break canonical,
+            Err(_) => match (existing.file_name(), existing.parent()) {
+                (Some(name), Some(parent)) => {
+                    missing_tail.push(name.to_os_string());
+                    existing = parent;
+                }
+                _ => return Err(format!("Failed to resolve path: {}", path.display())),
+            },
+        }
+    };
+
+    let mut resolved = canonical_existing;
+    for component in missing_tail.iter().rev() {
+        resolved.push(component);
+    }
+
+    if roots.iter().any(|root| resolved.starts_with(root)) {
+        Ok(resolved)
+    } else {
+        Err(format!("Path '{}' is outside the allowed project scope", resolved.display()))
+    }
+}
+
+#[tauri::command]
+fn add_allowed_path(path: String, allowed: tauri::State<'_, AllowedPaths>) -> Result<(), String> {
+    let canonical = PathBuf::from(&path)
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve path: {}", e))?;
+
+    allowed.0.lock().unwrap().push(canonical);
+    Ok(())
+}
+
+#[tauri::command]
+fn clear_allowed_paths(allowed: tauri::State<'_, AllowedPaths>) {
+    allowed.0.lock().unwrap().clear();
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 struct FileNode {
     name: String,
     path: String,
     #[serde(rename = "type")]
     node_type: String,
     children: Option<Vec<FileNode>>,
+    size: Option<u64>,
+    created: Option<u64>,
+    modified: Option<u64>,
+    accessed: Option<u64>,
+    is_symlink: bool,
+    #[cfg(unix)]
+    permissions: Option<String>,
+    item_count: Option<usize>,
+}
+
+// Unix-epoch milliseconds, or `None` if the platform/filesystem doesn't track this timestamp.
+fn system_time_to_millis(time: std::io::Result<std::time::SystemTime>) -> Option<u64> {
+    time.ok()
+        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|d| d.as_millis() as u64)
+}
+
+// Renders Unix mode bits as e.g. `0644 (rw-r--r--)`.
+#[cfg(unix)]
+fn format_permissions(mode: u32) -> String {
+    let perm_bits = mode & 0o777;
+
+    let triplet = |bits: u32| {
+        format!(
+            "{}{}{}",
+            if bits & 0o4 != 0 { "r" } else { "-" },
+            if bits & 0o2 != 0 { "w" } else { "-" },
+            if bits & 0o1 != 0 { "x" } else { "-" },
+        )
+    };
+
+    format!(
+        "{:04o} ({}{}{})",
+        perm_bits,
+        triplet((perm_bits >> 6) & 0o7),
+        triplet((perm_bits >> 3) & 0o7),
+        triplet(perm_bits & 0o7),
+    )
+}
+
+// Builds a `FileNode` from a directory entry's already-fetched metadata so
+// every walker populates the same fields from a single stat call.
+fn file_node_from_metadata(
+    path: &std::path::Path,
+    name: String,
+    metadata: &std::fs::Metadata,
+    children: Option<Vec<FileNode>>,
+) -> FileNode {
+    let node_type = if metadata.is_dir() { "folder" } else { "file" };
+    let size = if metadata.is_file() { Some(metadata.len()) } else { None };
+    #[cfg(unix)]
+    let permissions = {
+        use std::os::unix::fs::PermissionsExt;
+        Some(format_permissions(metadata.permissions().mode()))
+    };
+    // `None` here means "not a directory", but a directory whose recursive
+    // walk failed partway through also reports `children: None` (see the
+    // `.ok()` in `walk_tree`), so a readable-but-partially-failed folder is
+    // indistinguishable from a file here. Folders returned by `find_files`
+    // are always built without children, so their `item_count` is `None` too.
+    let item_count = children.as_ref().map(|c| c.len());
+
+    FileNode {
+        name,
+        path: path.to_string_lossy().to_string(),
+        node_type: node_type.to_string(),
+        children,
+        size,
+        created: system_time_to_millis(metadata.created()),
+        modified: system_time_to_millis(metadata.modified()),
+        accessed: system_time_to_millis(metadata.accessed()),
+        is_symlink: metadata.file_type().is_symlink(),
+        #[cfg(unix)]
+        permissions,
+        item_count,
+    }
 }
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
@@ -17,13 +163,18 @@ fn greet(name: &str) -> String {
 }
 
 #[tauri::command]
-async fn create_project_folder(project_path: &str, project_name: &str) -> Result<String, String> {
+async fn create_project_folder(
+    project_path: &str,
+    project_name: &str,
+    allowed: tauri::State<'_, AllowedPaths>,
+) -> Result<String, String> {
     use std::fs;
-    
+
     let full_path = PathBuf::from(project_path).join(project_name);
-    
-    match fs::create_dir_all(&full_path) {
-        Ok(_) => Ok(full_path.to_string_lossy().to_string()),
+    let resolved = resolve_within_scope(&full_path, &allowed)?;
+
+    match fs::create_dir_all(&resolved) {
+        Ok(_) => Ok(resolved.to_string_lossy().to_string()),
         Err(e) => Err(format!("Failed to create project folder: {}", e)),
     }
 }
@@ -48,91 +199,607 @@ async fn validate_project_folder(project_path: &str) -> Result<bool, String> {
 }
 
 #[tauri::command]
-async fn read_file_content(file_path: &str) -> Result<String, String> {
+async fn read_file_content(file_path: &str, allowed: tauri::State<'_, AllowedPaths>) -> Result<String, String> {
     use std::fs;
-    
-    match fs::read_to_string(file_path) {
+
+    let resolved = resolve_within_scope(Path::new(file_path), &allowed)?;
+
+    match fs::read_to_string(&resolved) {
         Ok(content) => Ok(content),
         Err(e) => Err(format!("Failed to read file: {}", e)),
     }
 }
 
+#[derive(Serialize, Deserialize, Debug)]
+struct FileContent {
+    mime_type: String,
+    is_binary: bool,
+    content: Option<String>,
+    base64: Option<String>,
+}
+
+// Guesses a MIME type from the file extension. Good enough for previewing in
+// the editor; falls back to `text/plain` for anything unrecognized.
+fn guess_mime_type(path: &std::path::Path) -> String {
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_lowercase();
+
+    let mime = match ext.as_str() {
+        "txt" | "md" | "markdown" | "gmi" | "log" | "csv" => "text/plain",
+        "html" | "htm" => "text/html",
+        "css" => "text/css",
+        "js" | "mjs" | "cjs" => "text/javascript",
+        "ts" | "tsx" | "jsx" => "text/typescript",
+        "json" => "application/json",
+        "xml" => "application/xml",
+        "yaml" | "yml" => "application/yaml",
+        "toml" => "application/toml",
+        "rs" | "py" | "go" | "java" | "c" | "h" | "cpp" | "hpp" | "sh" | "rb" | "php" => "text/plain",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "webp" => "image/webp",
+        "svg" => "image/svg+xml",
+        "ico" => "image/x-icon",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "wasm" => "application/wasm",
+        _ => "text/plain",
+    };
+
+    mime.to_string()
+}
+
+const BASE64_ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Standard (non-URL-safe) base64 encoding with `=` padding. Hand-rolled to
+// avoid pulling in an extra crate for a one-off encode.
+fn base64_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied();
+        let b2 = chunk.get(2).copied();
+
+        out.push(BASE64_ALPHABET[(b0 >> 2) as usize] as char);
+        out.push(BASE64_ALPHABET[((b0 & 0b11) << 4 | b1.unwrap_or(0) >> 4) as usize] as char);
+        out.push(match b1 {
+            Some(b1) => BASE64_ALPHABET[((b1 & 0b1111) << 2 | b2.unwrap_or(0) >> 6) as usize] as char,
+            None => '=',
+        });
+        out.push(match b2 {
+            Some(b2) => BASE64_ALPHABET[(b2 & 0b111111) as usize] as char,
+            None => '=',
+        });
+    }
+
+    out
+}
+
 #[tauri::command]
-async fn write_file_content(file_path: &str, content: &str) -> Result<(), String> {
+async fn read_file_content_detailed(
+    file_path: &str,
+    allowed: tauri::State<'_, AllowedPaths>,
+) -> Result<FileContent, String> {
     use std::fs;
-    
-    match fs::write(file_path, content) {
-        Ok(_) => Ok(()),
-        Err(e) => Err(format!("Failed to write file: {}", e)),
+
+    let path = resolve_within_scope(Path::new(file_path), &allowed)?;
+    let mime_type = guess_mime_type(&path);
+
+    let bytes = fs::read(&path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    match String::from_utf8(bytes) {
+        Ok(content) => Ok(FileContent {
+            mime_type,
+            is_binary: false,
+            content: Some(content),
+            base64: None,
+        }),
+        Err(e) => Ok(FileContent {
+            mime_type,
+            is_binary: true,
+            content: None,
+            base64: Some(base64_encode(&e.into_bytes())),
+        }),
     }
 }
 
+// Builds a sibling temp-file path for `dest`, e.g. `notes.txt` -> `notes.txt.<pid><nanos>.tmp`.
+fn temp_file_path(dest: &PathBuf) -> PathBuf {
+    let pid = std::process::id();
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    let file_name = dest.file_name().and_then(|n| n.to_str()).unwrap_or("file");
+    dest.with_file_name(format!("{}.{}{}.tmp", file_name, pid, nanos))
+}
+
 #[tauri::command]
-async fn get_folder_structure(folder_path: &str) -> Result<Vec<FileNode>, String> {
+async fn write_file_content(
+    file_path: &str,
+    content: &str,
+    allowed: tauri::State<'_, AllowedPaths>,
+) -> Result<(), String> {
     use std::fs;
-    
-    fn read_dir_recursive(path: &PathBuf) -> Result<Vec<FileNode>, String> {
-        let mut nodes = Vec::new();
-        
-        let entries = match fs::read_dir(path) {
-            Ok(entries) => entries,
-            Err(e) => return Err(format!("Failed to read directory: {}", e)),
-        };
-        
-        for entry in entries {
-            let entry = match entry {
-                Ok(e) => e,
-                Err(_) => continue,
-            };
-            
-            let path = entry.path();
-            let name = match path.file_name() {
-                Some(n) => n.to_string_lossy().to_string(),
-                None => continue,
+    use std::io::Write;
+
+    let dest = resolve_within_scope(Path::new(file_path), &allowed)?;
+
+    if let Some(parent) = dest.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create parent directory: {}", e))?;
+        }
+    }
+
+    let tmp_path = temp_file_path(&dest);
+
+    let write_result = (|| -> std::io::Result<()> {
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    })();
+
+    if let Err(e) = write_result {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(format!("Failed to write file: {}", e));
+    }
+
+    // Same-filesystem rename is atomic: readers never observe a partial write.
+    if let Err(e) = fs::rename(&tmp_path, &dest) {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(format!("Failed to write file: {}", e));
+    }
+
+    Ok(())
+}
+
+// One parsed line from a `.gitignore` file. `anchored` patterns (containing a
+// `/` other than a trailing one) only match relative to `base_dir`, the
+// root-relative directory the `.gitignore` file lives in (empty for the
+// project root); unanchored patterns match at any depth under `base_dir`.
+#[derive(Debug, Clone)]
+struct GitignoreRule {
+    pattern: String,
+    negate: bool,
+    dir_only: bool,
+    anchored: bool,
+    base_dir: String,
+}
+
+fn parse_gitignore(path: &PathBuf, base_dir: &str) -> Vec<GitignoreRule> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+
+    content
+        .lines()
+        .filter_map(|raw_line| {
+            let line = raw_line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+
+            let negate = line.starts_with('!');
+            let mut pattern = if negate { &line[1..] } else { line }.to_string();
+
+            let dir_only = pattern.ends_with('/');
+            if dir_only {
+                pattern.pop();
+            }
+
+            let anchored = pattern.trim_end_matches('/').contains('/');
+            let pattern = pattern.trim_start_matches('/').to_string();
+
+            Some(GitignoreRule { pattern, negate, dir_only, anchored, base_dir: base_dir.to_string() })
+        })
+        .collect()
+}
+
+// Matches a single path segment (no `/` in `pattern` or `text`): `*` matches
+// any run of characters within the segment, `?` matches exactly one.
+fn glob_match_segment(pattern: &str, text: &str) -> bool {
+    fn helper(p: &[char], t: &[char]) -> bool {
+        match p.first() {
+            None => t.is_empty(),
+            Some('*') => helper(&p[1..], t) || (!t.is_empty() && helper(p, &t[1..])),
+            Some('?') => !t.is_empty() && helper(&p[1..], &t[1..]),
+            Some(c) => !t.is_empty() && t[0] == *c && helper(&p[1..], &t[1..]),
+        }
+    }
+
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    helper(&p, &t)
+}
+
+// Minimal glob matcher over `/`-separated paths, matching gitignore/glob
+// semantics: `*` and `?` never cross a `/`, and a `**` path segment matches
+// zero or more whole path segments.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    fn helper(p: &[&str], t: &[&str]) -> bool {
+        match p.first() {
+            None => t.is_empty(),
+            Some(&"**") => helper(&p[1..], t) || (!t.is_empty() && helper(p, &t[1..])),
+            Some(seg) => !t.is_empty() && glob_match_segment(seg, t[0]) && helper(&p[1..], &t[1..]),
+        }
+    }
+
+    let pattern_segs: Vec<&str> = pattern.split('/').collect();
+    let text_segs: Vec<&str> = text.split('/').collect();
+    helper(&pattern_segs, &text_segs)
+}
+
+// Later rules win, mirroring git's "last match decides" semantics, so the
+// caller just needs to append deeper `.gitignore` rules onto the stack.
+fn is_ignored(rules: &[GitignoreRule], rel_path: &str, is_dir: bool) -> bool {
+    let mut ignored = false;
+
+    for rule in rules {
+        if rule.dir_only && !is_dir {
+            continue;
+        }
+
+        let matches = if rule.anchored {
+            // `inherited_rules` only ever carries a rule into directories at
+            // or below its own, so `rel_path` is guaranteed to fall under
+            // `base_dir`; strip it so the pattern is matched relative to the
+            // `.gitignore` file that defined it, not the project root.
+            let rel_to_base = if rule.base_dir.is_empty() {
+                rel_path
+            } else {
+                rel_path.strip_prefix(&rule.base_dir).and_then(|s| s.strip_prefix('/')).unwrap_or(rel_path)
             };
-            
-            // Skip hidden files and common ignore patterns
-            if name.starts_with('.') || name == "node_modules" || name == "target" || name == "dist" {
+            glob_match(&rule.pattern, rel_to_base)
+        } else {
+            glob_match(&rule.pattern, rel_path)
+                || rel_path.rsplit('/').next().map_or(false, |name| glob_match(&rule.pattern, name))
+        };
+
+        if matches {
+            ignored = !rule.negate;
+        }
+    }
+
+    ignored
+}
+
+// Shared recursive walker behind `get_folder_structure` and `scan_project`.
+// `on_entry` is invoked once per file/folder seen, after gitignore filtering,
+// so both commands report progress (or just counts) from the same walk.
+fn walk_tree(
+    path: &PathBuf,
+    root: &PathBuf,
+    respect_gitignore: bool,
+    extra_ignores: &[String],
+    inherited_rules: &[GitignoreRule],
+    entries_seen: &mut usize,
+    on_entry: &mut dyn FnMut(usize, &str),
+) -> Result<Vec<FileNode>, String> {
+    use std::fs;
+
+    let mut nodes = Vec::new();
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(e) => return Err(format!("Failed to read directory: {}", e)),
+    };
+
+    // Rules from this directory's own `.gitignore` are appended on top of
+    // the ones inherited from ancestors, so they take precedence.
+    let mut rules = inherited_rules.to_vec();
+    if respect_gitignore {
+        let base_dir = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        rules.extend(parse_gitignore(&path.join(".gitignore"), &base_dir));
+    }
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        let path = entry.path();
+        let name = match path.file_name() {
+            Some(n) => n.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        // `.git` itself is never useful in a project tree, gitignore or not.
+        if name == ".git" {
+            continue;
+        }
+
+        let metadata = match entry.metadata() {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        let rel_path = path
+            .strip_prefix(root)
+            .unwrap_or(&path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        // `extra_ignores` force-hides entries regardless of whether gitignore
+        // filtering is on, so it's checked unconditionally.
+        if extra_ignores.iter().any(|pattern| glob_match(pattern, &name) || glob_match(pattern, &rel_path)) {
+            continue;
+        }
+
+        if respect_gitignore {
+            if is_ignored(&rules, &rel_path, metadata.is_dir()) {
                 continue;
             }
-            
-            let metadata = match entry.metadata() {
-                Ok(m) => m,
-                Err(_) => continue,
-            };
-            
-            if metadata.is_dir() {
-                let children = read_dir_recursive(&path).ok();
-                nodes.push(FileNode {
-                    name,
-                    path: path.to_string_lossy().to_string(),
-                    node_type: "folder".to_string(),
-                    children,
-                });
-            } else {
-                nodes.push(FileNode {
-                    name,
-                    path: path.to_string_lossy().to_string(),
-                    node_type: "file".to_string(),
-                    children: None,
-                });
+        } else if name.starts_with('.') || name == "node_modules" || name == "target" || name == "dist" {
+            continue;
+        }
+
+        *entries_seen += 1;
+        on_entry(*entries_seen, &path.to_string_lossy());
+
+        let children = if metadata.is_dir() {
+            walk_tree(&path, root, respect_gitignore, extra_ignores, &rules, entries_seen, on_entry).ok()
+        } else {
+            None
+        };
+
+        nodes.push(file_node_from_metadata(&path, name, &metadata, children));
+    }
+
+    // Sort: folders first, then files, alphabetically
+    nodes.sort_by(|a, b| {
+        match (&a.node_type == "folder", &b.node_type == "folder") {
+            (true, false) => std::cmp::Ordering::Less,
+            (false, true) => std::cmp::Ordering::Greater,
+            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+        }
+    });
+
+    Ok(nodes)
+}
+
+// Cache entries are keyed on the folder path plus the filtering options used
+// to build the tree, so a lookup with different `respect_gitignore`/
+// `extra_ignores` never returns a tree filtered a different way. The path
+// always comes first and is NUL-separated from the options so
+// `invalidate_scan_cache` can drop every variant for a path by prefix.
+fn scan_cache_key(folder_path: &str, respect_gitignore: bool, extra_ignores: &[String]) -> String {
+    let mut sorted_ignores = extra_ignores.to_vec();
+    sorted_ignores.sort();
+    format!("{}\0{}\0{}", folder_path, respect_gitignore, sorted_ignores.join(","))
+}
+
+#[tauri::command]
+async fn get_folder_structure(
+    folder_path: &str,
+    respect_gitignore: Option<bool>,
+    extra_ignores: Option<Vec<String>>,
+    index: tauri::State<'_, ProjectIndex>,
+    allowed: tauri::State<'_, AllowedPaths>,
+) -> Result<Vec<FileNode>, String> {
+    let respect_gitignore = respect_gitignore.unwrap_or(true);
+    let extra_ignores = extra_ignores.unwrap_or_default();
+
+    // Resolve (and enforce) scope before ever touching the cache, so a
+    // closed/narrowed scope rejects the call even on what would otherwise be
+    // a cache hit.
+    let root = resolve_within_scope(Path::new(folder_path), &allowed)?;
+    let cache_key = scan_cache_key(&root.to_string_lossy(), respect_gitignore, &extra_ignores);
+
+    if let Some(cached) = index.0.lock().unwrap().get(&cache_key) {
+        return Ok(cached.clone());
+    }
+
+    let mut entries_seen = 0usize;
+    let tree = walk_tree(&root, &root, respect_gitignore, &extra_ignores, &[], &mut entries_seen, &mut |_, _| {})?;
+
+    index.0.lock().unwrap().insert(cache_key, tree.clone());
+    Ok(tree)
+}
+
+// The cache is keyed per tree root, not per subdirectory, so there is no way
+// to invalidate just a subtree: this always drops every cached variant
+// (every `respect_gitignore`/`extra_ignores` combination) for the whole
+// `folder_path` root, even if only one subdirectory actually changed.
+#[tauri::command]
+fn invalidate_scan_cache(folder_path: String, index: tauri::State<'_, ProjectIndex>) {
+    // Keys hold the canonicalized root, so canonicalize here too; fall back to
+    // the path as given if it no longer exists.
+    let root = PathBuf::from(&folder_path)
+        .canonicalize()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or(folder_path);
+    let prefix = format!("{}\0", root);
+    index.0.lock().unwrap().retain(|key, _| !key.starts_with(&prefix));
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct ScanProgress {
+    files_seen: usize,
+    current_path: String,
+}
+
+#[derive(Serialize, Clone, Debug)]
+struct ScanComplete {
+    root: String,
+    tree: Vec<FileNode>,
+}
+
+// Walks `folder_path` on a blocking task so the UI thread stays responsive,
+// emitting `scan-progress` as entries are found and `scan-complete` with the
+// full tree at the end. The result is cached in `ProjectIndex` under the
+// default options (gitignore respected, no extra ignores), so a subsequent
+// `get_folder_structure` call for the same path with those options is served
+// from memory instead of hitting disk again.
+#[tauri::command]
+async fn scan_project(
+    app_handle: tauri::AppHandle,
+    index: tauri::State<'_, ProjectIndex>,
+    allowed: tauri::State<'_, AllowedPaths>,
+    folder_path: String,
+) -> Result<Vec<FileNode>, String> {
+    let root = resolve_within_scope(Path::new(&folder_path), &allowed)?;
+    let root_for_walk = root.clone();
+    let progress_handle = app_handle.clone();
+
+    let tree = tauri::async_runtime::spawn_blocking(move || {
+        let mut entries_seen = 0usize;
+        walk_tree(&root_for_walk, &root_for_walk, true, &[], &[], &mut entries_seen, &mut |files_seen, current_path| {
+            let _ = progress_handle.emit(
+                "scan-progress",
+                ScanProgress { files_seen, current_path: current_path.to_string() },
+            );
+        })
+    })
+    .await
+    .map_err(|e| format!("Scan task panicked: {}", e))??;
+
+    let cache_key = scan_cache_key(&root.to_string_lossy(), true, &[]);
+    index.0.lock().unwrap().insert(cache_key, tree.clone());
+
+    let _ = app_handle.emit("scan-complete", ScanComplete { root: folder_path, tree: tree.clone() });
+
+    Ok(tree)
+}
+
+// True if a directory at `rel_dir` could still contain something matching
+// `pattern`, so the walker can skip descending into directories a pattern's
+// literal prefix has already ruled out. A `**` component frees the rest of
+// the pattern, since it can expand to any number of path segments.
+fn pattern_could_descend(pattern: &str, rel_dir: &str) -> bool {
+    let pattern_components: Vec<&str> = pattern.split('/').collect();
+    let dir_components: Vec<&str> = if rel_dir.is_empty() { Vec::new() } else { rel_dir.split('/').collect() };
+
+    for (i, dir_comp) in dir_components.iter().enumerate() {
+        match pattern_components.get(i) {
+            None => return false,
+            Some(&"**") => return true,
+            Some(p_comp) => {
+                if !glob_match(p_comp, dir_comp) {
+                    return false;
+                }
             }
         }
-        
-        // Sort: folders first, then files, alphabetically
-        nodes.sort_by(|a, b| {
-            match (&a.node_type == "folder", &b.node_type == "folder") {
-                (true, false) => std::cmp::Ordering::Less,
-                (false, true) => std::cmp::Ordering::Greater,
-                _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    }
+
+    true
+}
+
+// Splits `!`-prefixed exclude patterns from plain include patterns.
+fn split_include_exclude(patterns: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut includes = Vec::new();
+    let mut excludes = Vec::new();
+
+    for pattern in patterns {
+        if let Some(stripped) = pattern.strip_prefix('!') {
+            excludes.push(stripped.to_string());
+        } else {
+            includes.push(pattern.clone());
+        }
+    }
+
+    (includes, excludes)
+}
+
+fn collect_glob_matches(
+    path: &PathBuf,
+    root: &PathBuf,
+    includes: &[String],
+    excludes: &[String],
+    inherited_rules: &[GitignoreRule],
+    matches: &mut Vec<FileNode>,
+) {
+    use std::fs;
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+
+    let mut rules = inherited_rules.to_vec();
+    let base_dir = path.strip_prefix(root).unwrap_or(path).to_string_lossy().replace('\\', "/");
+    rules.extend(parse_gitignore(&path.join(".gitignore"), &base_dir));
+
+    for entry in entries {
+        let entry = match entry {
+            Ok(e) => e,
+            Err(_) => continue,
+        };
+
+        let entry_path = entry.path();
+        let name = match entry_path.file_name() {
+            Some(n) => n.to_string_lossy().to_string(),
+            None => continue,
+        };
+
+        if name == ".git" {
+            continue;
+        }
+
+        let metadata = match entry.metadata() {
+            Ok(m) => m,
+            Err(_) => continue,
+        };
+
+        let rel_path = entry_path
+            .strip_prefix(root)
+            .unwrap_or(&entry_path)
+            .to_string_lossy()
+            .replace('\\', "/");
+
+        if is_ignored(&rules, &rel_path, metadata.is_dir()) {
+            continue;
+        }
+
+        if excludes.iter().any(|pattern| glob_match(pattern, &rel_path)) {
+            continue;
+        }
+
+        let is_match = includes.iter().any(|pattern| glob_match(pattern, &rel_path));
+
+        if metadata.is_dir() {
+            if includes.iter().any(|pattern| pattern_could_descend(pattern, &rel_path)) {
+                collect_glob_matches(&entry_path, root, includes, excludes, &rules, matches);
             }
-        });
-        
-        Ok(nodes)
+            if is_match {
+                matches.push(file_node_from_metadata(&entry_path, name, &metadata, None));
+            }
+        } else if is_match {
+            matches.push(file_node_from_metadata(&entry_path, name, &metadata, None));
+        }
     }
-    
-    let path = PathBuf::from(folder_path);
-    read_dir_recursive(&path)
+}
+
+// Finds files (and directories) under `root` matching any of `patterns`
+// (e.g. `src/**/*.rs`), with `!`-prefixed patterns excluding matches. Applies
+// the same gitignore rules as `get_folder_structure` and avoids descending
+// into directories no pattern's literal prefix could reach.
+#[tauri::command]
+async fn find_files(
+    root: String,
+    patterns: Vec<String>,
+    allowed: tauri::State<'_, AllowedPaths>,
+) -> Result<Vec<FileNode>, String> {
+    let (includes, excludes) = split_include_exclude(&patterns);
+    let root_path = resolve_within_scope(Path::new(&root), &allowed)?;
+
+    let mut matches = Vec::new();
+    collect_glob_matches(&root_path, &root_path, &includes, &excludes, &[], &mut matches);
+
+    matches.sort_by(|a, b| match (&a.node_type == "folder", &b.node_type == "folder") {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    });
+
+    Ok(matches)
 }
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
@@ -140,13 +807,21 @@ pub fn run() {
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
+        .manage(ProjectIndex(Mutex::new(HashMap::new())))
+        .manage(AllowedPaths(Mutex::new(Vec::new())))
         .invoke_handler(tauri::generate_handler![
-            greet, 
-            create_project_folder, 
+            greet,
+            create_project_folder,
             validate_project_folder,
             read_file_content,
+            read_file_content_detailed,
             write_file_content,
-            get_folder_structure
+            get_folder_structure,
+            scan_project,
+            invalidate_scan_cache,
+            find_files,
+            add_allowed_path,
+            clear_allowed_paths
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");